@@ -26,8 +26,8 @@ use crate::{
             },
             transparent::{self, builder::TransparentBuilder},
         },
-        sighash::{signature_hash, SignableInput},
-        txid::TxIdDigester,
+        sighash::{signature_hash, Blake2bHash, SignableInput},
+        txid::{TxDigests, TxIdDigester},
         Transaction, TransactionData, TxVersion, Unauthorized,
     },
     zip32::ExtendedSpendingKey,
@@ -53,8 +53,12 @@ const DEFAULT_TX_EXPIRY_DELTA: u32 = 20;
 #[derive(Debug)]
 pub enum Error {
     ChangeIsNegative(Amount),
+    InsufficientFunds { available: Amount, required: Amount },
     InvalidAmount,
     NoChangeAddress,
+    /// A transparent input signing operation was requested for an index that doesn't
+    /// correspond to any input added to the builder.
+    TransparentInputOutOfBounds(usize),
     TransparentBuild(transparent::builder::Error),
     SaplingBuild(sapling::builder::Error),
     OrchardBuild(orchard::builder::Error),
@@ -70,8 +74,16 @@ impl fmt::Display for Error {
             Error::ChangeIsNegative(amount) => {
                 write!(f, "Change is negative ({:?} zatoshis)", amount)
             }
+            Error::InsufficientFunds { available, required } => write!(
+                f,
+                "Insufficient funds: have {:?}, need {:?}",
+                available, required
+            ),
             Error::InvalidAmount => write!(f, "Invalid amount"),
             Error::NoChangeAddress => write!(f, "No change address specified or discoverable"),
+            Error::TransparentInputOutOfBounds(index) => {
+                write!(f, "No transparent input at index {}", index)
+            }
             Error::TransparentBuild(err) => err.fmt(f),
             Error::SaplingBuild(err) => err.fmt(f),
             Error::OrchardBuild(err) => write!(f, "{:?}", err),
@@ -90,8 +102,16 @@ impl PartialEq for Error {
     fn eq(&self, other: &Self) -> bool {
         match (self, other) {
             (Error::ChangeIsNegative(e), Error::ChangeIsNegative(f)) => e == f,
+            (
+                Error::InsufficientFunds { available: a1, required: r1 },
+                Error::InsufficientFunds { available: a2, required: r2 },
+            ) => a1 == a2 && r1 == r2,
             (Error::InvalidAmount, Error::InvalidAmount) => true,
             (Error::NoChangeAddress, Error::NoChangeAddress) => true,
+            (
+                Error::TransparentInputOutOfBounds(i),
+                Error::TransparentInputOutOfBounds(j),
+            ) => i == j,
             (Error::TransparentBuild(e), Error::TransparentBuild(f)) => e == f,
             (Error::SaplingBuild(e), Error::SaplingBuild(f)) => e == f,
             (Error::OrchardBuild(e), Error::OrchardBuild(f)) => {
@@ -108,6 +128,477 @@ impl PartialEq for Error {
 
 impl error::Error for Error {}
 
+/// Produces Sapling Spend proofs.
+///
+/// This is split out of the monolithic [`TxProver`] so that the Groth16 proof for a
+/// Spend can be produced out of band from bundle assembly — on a different thread, on a
+/// different machine, or by a specialized batch prover — instead of being driven inline
+/// by [`Builder::build`].
+pub trait SpendProver {
+    /// An instantiated Spend circuit, ready to be proved.
+    type Circuit;
+    /// A Groth16 proof for a Spend circuit.
+    type Proof;
+
+    /// Instantiates the Spend circuit for a single Sapling spend.
+    fn prepare_circuit(&self, spend: &sapling::builder::SpendDescriptionInfo) -> Self::Circuit;
+
+    /// Produces a Groth16 proof for a previously-instantiated Spend circuit.
+    fn create_proof(
+        &self,
+        circuit: Self::Circuit,
+        rng: &mut (impl RngCore + CryptoRng),
+    ) -> Self::Proof;
+}
+
+/// Produces Sapling Output proofs, split out of [`TxProver`] for the same reasons as
+/// [`SpendProver`].
+pub trait OutputProver {
+    /// An instantiated Output circuit, ready to be proved.
+    type Circuit;
+    /// A Groth16 proof for an Output circuit.
+    type Proof;
+
+    /// Instantiates the Output circuit for a single Sapling output.
+    fn prepare_circuit(&self, output: &sapling::builder::OutputDescriptionInfo) -> Self::Circuit;
+
+    /// Produces a Groth16 proof for a previously-instantiated Output circuit.
+    fn create_proof(
+        &self,
+        circuit: Self::Circuit,
+        rng: &mut (impl RngCore + CryptoRng),
+    ) -> Self::Proof;
+}
+
+/// An assembled transaction whose Sapling Spend and Output proofs have not yet been
+/// created, returned by [`Builder::build_unproven`].
+///
+/// The transparent and Orchard bundles are already assembled (the Orchard proof is
+/// already created) but not yet signed; the Sapling bundle instead holds prepared,
+/// unproven circuit instances. Call [`UnprovenTransaction::prove`] with
+/// a matching [`SpendProver`]/[`OutputProver`] pair to fill in the Sapling proofs and
+/// obtain the finished, signed [`Transaction`].
+///
+/// This lets callers batch-prove many transactions, run proving on a different thread or
+/// machine than note selection, or inject a parallel prover, without duplicating the
+/// bundle-assembly logic that already lives in [`Builder::build`].
+pub struct UnprovenTransaction {
+    version: TxVersion,
+    consensus_branch_id: BranchId,
+    expiry_height: BlockHeight,
+    transparent_bundle: Option<transparent::Bundle<transparent::Authorized>>,
+    unproven_sapling_bundle: Option<sapling::builder::UnprovenBundle>,
+    orchard_bundle: Option<orchard::Bundle<orchard::bundle::Authorized, Amount>>,
+    orchard_spending_keys: Vec<orchard::keys::SpendAuthorizingKey>,
+    #[cfg(feature = "zfuture")]
+    tze_bundle: Option<tze::Bundle<tze::Authorized>>,
+}
+
+impl UnprovenTransaction {
+    /// Produces the Sapling Spend and Output proofs with the given provers, then signs
+    /// and finalizes the transaction.
+    pub fn prove(
+        self,
+        spend_prover: &impl SpendProver,
+        output_prover: &impl OutputProver,
+        mut rng: impl RngCore + CryptoRng,
+    ) -> Result<(Transaction, SaplingMetadata), Error> {
+        let sapling_bundle = self
+            .unproven_sapling_bundle
+            .map(|b| b.create_proofs(spend_prover, output_prover, &mut rng))
+            .transpose()
+            .map_err(Error::SaplingBuild)?;
+
+        let unauthed_tx: TransactionData<Unauthorized> = TransactionData {
+            version: self.version,
+            consensus_branch_id: self.consensus_branch_id,
+            lock_time: 0,
+            expiry_height: self.expiry_height,
+            transparent_bundle: self.transparent_bundle,
+            sprout_bundle: None,
+            sapling_bundle,
+            orchard_bundle: self.orchard_bundle,
+            #[cfg(feature = "zfuture")]
+            tze_bundle: self.tze_bundle,
+        };
+
+        let txid_parts = unauthed_tx.digest(TxIdDigester);
+
+        let transparent_bundle = unauthed_tx.transparent_bundle.clone().map(|b| {
+            b.apply_signatures(
+                #[cfg(feature = "transparent-inputs")]
+                &unauthed_tx,
+                #[cfg(feature = "transparent-inputs")]
+                &txid_parts,
+            )
+        });
+
+        let shielded_sig_commitment =
+            signature_hash(&unauthed_tx, &SignableInput::Shielded, &txid_parts);
+
+        let (sapling_bundle, tx_metadata) = match unauthed_tx
+            .sapling_bundle
+            .map(|b| b.apply_signatures(&mut rng, shielded_sig_commitment.as_ref()))
+            .transpose()
+            .map_err(Error::SaplingBuild)?
+        {
+            Some((bundle, meta)) => (Some(bundle), meta),
+            None => (None, SaplingMetadata::empty()),
+        };
+
+        let orchard_saks = self.orchard_spending_keys;
+
+        let orchard_bundle = unauthed_tx
+            .orchard_bundle
+            .map(|b| b.apply_signatures(&mut rng, *shielded_sig_commitment.as_ref(), &orchard_saks))
+            .transpose()
+            .map_err(Error::OrchardBuild)?;
+
+        let authorized_tx = TransactionData {
+            version: unauthed_tx.version,
+            consensus_branch_id: unauthed_tx.consensus_branch_id,
+            lock_time: unauthed_tx.lock_time,
+            expiry_height: unauthed_tx.expiry_height,
+            transparent_bundle,
+            sprout_bundle: unauthed_tx.sprout_bundle,
+            sapling_bundle,
+            orchard_bundle,
+            #[cfg(feature = "zfuture")]
+            tze_bundle,
+        };
+
+        Ok((authorized_tx.freeze().unwrap(), tx_metadata))
+    }
+}
+
+/// A transaction whose transparent, Sapling, and Orchard bundles are fully assembled
+/// (including Sapling/Orchard proofs) but which carries no spend authorization or
+/// binding signatures yet.
+///
+/// Returned by [`Builder::build_for_external_signing`] for hardware-wallet / remote-
+/// signer flows: the partially-built transaction can be serialized and handed to an
+/// external device to produce the required signatures, then re-ingested via
+/// [`PartiallyBuiltTransaction::apply_external_signatures`] to obtain the final
+/// [`Transaction`]. The `Builder` is not required to hold the Orchard/Sapling spend
+/// authorizing keys beyond this point: like [`UnauthorizedTransactionBundle`],
+/// [`PartiallyBuiltTransaction`] exposes only the raw per-spend randomizers needed to
+/// produce a signature, never the keys themselves.
+pub struct PartiallyBuiltTransaction {
+    unauthed_tx: TransactionData<Unauthorized>,
+    txid_parts: TxDigests<Blake2bHash>,
+    #[cfg(feature = "transparent-inputs")]
+    transparent_coins: Vec<TxOut>,
+    sapling_signing_parts: Vec<SaplingSigningParts>,
+    orchard_signing_parts: Vec<OrchardSigningParts>,
+    bsk: jubjub::Fr,
+}
+
+impl PartiallyBuiltTransaction {
+    /// Returns the assembled-but-unsigned transaction, for serialization and inspection
+    /// by an external signer.
+    pub fn unauthorized_tx(&self) -> &TransactionData<Unauthorized> {
+        &self.unauthed_tx
+    }
+
+    /// Returns the sighash that must be signed to authorize the transparent input at
+    /// `index` with the given sighash type.
+    ///
+    /// The signed data is derived from the coin being spent (its `script_pubkey` and
+    /// `value`, as supplied to [`Builder::add_transparent_input`]), not from this
+    /// transaction's own outputs.
+    #[cfg(feature = "transparent-inputs")]
+    pub fn transparent_sighash(&self, index: usize, hash_type: u8) -> Result<Blake2bHash, Error> {
+        let coin = self
+            .transparent_coins
+            .get(index)
+            .ok_or(Error::TransparentInputOutOfBounds(index))?;
+
+        Ok(signature_hash(
+            &self.unauthed_tx,
+            &SignableInput::Transparent {
+                hash_type,
+                index,
+                script_code: &coin.script_pubkey,
+                script_pubkey: &coin.script_pubkey,
+                value: coin.value,
+            },
+            &self.txid_parts,
+        ))
+    }
+
+    /// Returns the sighash shared by all Sapling spend authorization signatures and the
+    /// Orchard spend authorization / binding signatures.
+    pub fn shielded_sighash(&self) -> Blake2bHash {
+        signature_hash(&self.unauthed_tx, &SignableInput::Shielded, &self.txid_parts)
+    }
+
+    /// Returns the per-spend randomizers for the Sapling bundle, in the same order as
+    /// the Sapling spends were added to the `Builder`.
+    pub fn sapling_signing_parts(&self) -> &[SaplingSigningParts] {
+        &self.sapling_signing_parts
+    }
+
+    /// Returns the per-action randomizers for the Orchard bundle, one for each action in
+    /// the bundle (including dummy and output-only actions), in the bundle's action order.
+    pub fn orchard_signing_parts(&self) -> &[OrchardSigningParts] {
+        &self.orchard_signing_parts
+    }
+
+    /// Returns the accumulated binding signature signing key, `bsk`.
+    pub fn binding_signing_key(&self) -> jubjub::Fr {
+        self.bsk
+    }
+
+    /// Finalizes the transaction using signatures produced by an external signer.
+    ///
+    /// `sapling_spend_auth_sigs` and `orchard_spend_auth_sigs` must be in the same
+    /// order as [`PartiallyBuiltTransaction::sapling_signing_parts`] and
+    /// [`PartiallyBuiltTransaction::orchard_signing_parts`] respectively. The
+    /// transparent inputs are signed internally, as they always have been by
+    /// [`Builder::build`].
+    pub fn apply_external_signatures(
+        self,
+        sapling_spend_auth_sigs: Vec<[u8; 64]>,
+        orchard_spend_auth_sigs: Vec<[u8; 64]>,
+        binding_sig: [u8; 64],
+    ) -> Result<(Transaction, SaplingMetadata), Error> {
+        let transparent_bundle = self.unauthed_tx.transparent_bundle.clone().map(|b| {
+            b.apply_signatures(
+                #[cfg(feature = "transparent-inputs")]
+                &self.unauthed_tx,
+                #[cfg(feature = "transparent-inputs")]
+                &self.txid_parts,
+            )
+        });
+
+        let (sapling_bundle, tx_metadata) = match self
+            .unauthed_tx
+            .sapling_bundle
+            .map(|b| b.apply_external_signatures(sapling_spend_auth_sigs, binding_sig))
+            .transpose()
+            .map_err(Error::SaplingBuild)?
+        {
+            Some((bundle, meta)) => (Some(bundle), meta),
+            None => (None, SaplingMetadata::empty()),
+        };
+
+        let orchard_bundle = self
+            .unauthed_tx
+            .orchard_bundle
+            .map(|b| b.apply_external_signatures(orchard_spend_auth_sigs, binding_sig))
+            .transpose()
+            .map_err(Error::OrchardBuild)?;
+
+        let authorized_tx = TransactionData {
+            version: self.unauthed_tx.version,
+            consensus_branch_id: self.unauthed_tx.consensus_branch_id,
+            lock_time: self.unauthed_tx.lock_time,
+            expiry_height: self.unauthed_tx.expiry_height,
+            transparent_bundle,
+            sprout_bundle: self.unauthed_tx.sprout_bundle,
+            sapling_bundle,
+            orchard_bundle,
+            #[cfg(feature = "zfuture")]
+            tze_bundle: self.unauthed_tx.tze_bundle,
+        };
+
+        Ok((authorized_tx.freeze().unwrap(), tx_metadata))
+    }
+}
+
+/// The randomizers needed to produce a single Sapling spend authorization signature
+/// without access to the spend authorizing key (`ask`) itself: `alpha`, the key
+/// re-randomization scalar, and `rcv`, the value commitment randomness.
+#[derive(Debug, Clone, Copy)]
+pub struct SaplingSigningParts {
+    pub alpha: jubjub::Fr,
+    pub rcv: jubjub::Fr,
+}
+
+/// The randomizer needed to produce a single Orchard action's spend authorization
+/// signature without access to the spend authorizing key itself.
+#[derive(Debug, Clone, Copy)]
+pub struct OrchardSigningParts {
+    pub alpha: pasta_curves::Fq,
+}
+
+/// A fully-assembled, proved, but unsigned transaction, together with the signing
+/// material needed to authorize it, as produced by [`Builder::build_unauthorized`].
+///
+/// Like [`PartiallyBuiltTransaction`] (returned by
+/// [`Builder::build_for_external_signing`]), this does not retain the Orchard spend
+/// authorizing keys: the raw randomizers exposed here are everything an external signer
+/// needs to produce the spend authorization and binding signatures, so the `Builder`
+/// need not have held onto the keys themselves past bundle assembly.
+pub struct UnauthorizedTransactionBundle {
+    unauthed_tx: TransactionData<Unauthorized>,
+    sighash: Blake2bHash,
+    sapling_signing_parts: Vec<SaplingSigningParts>,
+    orchard_signing_parts: Vec<OrchardSigningParts>,
+    bsk: jubjub::Fr,
+}
+
+impl UnauthorizedTransactionBundle {
+    /// Returns the assembled-but-unsigned transaction.
+    pub fn unauthorized_tx(&self) -> &TransactionData<Unauthorized> {
+        &self.unauthed_tx
+    }
+
+    /// Returns the sighash shared by every spend authorization and binding signature in
+    /// this transaction.
+    pub fn sighash(&self) -> Blake2bHash {
+        self.sighash
+    }
+
+    /// Returns the per-spend randomizers for the Sapling bundle, in the same order as
+    /// the Sapling spends were added to the `Builder`.
+    pub fn sapling_signing_parts(&self) -> &[SaplingSigningParts] {
+        &self.sapling_signing_parts
+    }
+
+    /// Returns the per-action randomizers for the Orchard bundle, one for each action in
+    /// the bundle (including dummy and output-only actions), in the bundle's action order.
+    pub fn orchard_signing_parts(&self) -> &[OrchardSigningParts] {
+        &self.orchard_signing_parts
+    }
+
+    /// Returns the accumulated binding signature signing key, `bsk`.
+    pub fn binding_signing_key(&self) -> jubjub::Fr {
+        self.bsk
+    }
+
+    /// Validates and assembles the final [`Transaction`] from the raw signatures
+    /// produced by an external signer for each Sapling spend, each Orchard action, and
+    /// the shielded binding signature.
+    ///
+    /// `sapling_spend_auth_sigs` and `orchard_spend_auth_sigs` must be in the same
+    /// order as [`UnauthorizedTransactionBundle::sapling_signing_parts`] and
+    /// [`UnauthorizedTransactionBundle::orchard_signing_parts`] respectively.
+    pub fn apply_signatures(
+        self,
+        sapling_spend_auth_sigs: Vec<[u8; 64]>,
+        orchard_spend_auth_sigs: Vec<[u8; 64]>,
+        binding_sig: [u8; 64],
+    ) -> Result<(Transaction, SaplingMetadata), Error> {
+        let (sapling_bundle, tx_metadata) = match self
+            .unauthed_tx
+            .sapling_bundle
+            .map(|b| b.apply_external_signatures(sapling_spend_auth_sigs, binding_sig))
+            .transpose()
+            .map_err(Error::SaplingBuild)?
+        {
+            Some((bundle, meta)) => (Some(bundle), meta),
+            None => (None, SaplingMetadata::empty()),
+        };
+
+        let orchard_bundle = self
+            .unauthed_tx
+            .orchard_bundle
+            .map(|b| b.apply_external_signatures(orchard_spend_auth_sigs, binding_sig))
+            .transpose()
+            .map_err(Error::OrchardBuild)?;
+
+        let authorized_tx = TransactionData {
+            version: self.unauthed_tx.version,
+            consensus_branch_id: self.unauthed_tx.consensus_branch_id,
+            lock_time: self.unauthed_tx.lock_time,
+            expiry_height: self.unauthed_tx.expiry_height,
+            transparent_bundle: self.unauthed_tx.transparent_bundle,
+            sprout_bundle: self.unauthed_tx.sprout_bundle,
+            sapling_bundle,
+            orchard_bundle,
+            #[cfg(feature = "zfuture")]
+            tze_bundle: self.unauthed_tx.tze_bundle,
+        };
+
+        Ok((authorized_tx.freeze().unwrap(), tx_metadata))
+    }
+}
+
+/// Controls how many dummy, zero-value spends/outputs a shielded bundle is padded with
+/// before it is finalized.
+///
+/// Padding hides the true number of real spends/outputs a transaction contains, but
+/// callers that are building many transactions (e.g. for benchmarking) or that need a
+/// deterministic action count (e.g. to match a precomputed ZIP 317 fee) can opt out of
+/// it or pin it to an exact value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BundlePaddingRule {
+    /// Do not add any dummy spends/outputs.
+    None,
+    /// Pad to the bundle's privacy-preserving minimum (two Sapling outputs, or the
+    /// ZIP 317 grace-action count for Orchard).
+    PadToThreshold,
+    /// Pad to exactly `n` actions.
+    Exact(usize),
+}
+
+impl BundlePaddingRule {
+    /// Returns the number of actions the bundle should be padded up to, or `None` if no
+    /// padding should be added.
+    fn target(&self, default_threshold: usize) -> Option<usize> {
+        match self {
+            BundlePaddingRule::None => None,
+            BundlePaddingRule::PadToThreshold => Some(default_threshold),
+            BundlePaddingRule::Exact(n) => Some(*n),
+        }
+    }
+}
+
+impl Default for BundlePaddingRule {
+    fn default() -> Self {
+        BundlePaddingRule::PadToThreshold
+    }
+}
+
+/// General per-build configuration for a [`Builder`], covering concerns that apply to
+/// the transaction as a whole rather than to any single spend or output.
+#[derive(Debug, Clone)]
+pub struct BuildConfig {
+    sapling_padding: BundlePaddingRule,
+    orchard_padding: BundlePaddingRule,
+    shuffle_bundle_order: bool,
+}
+
+impl Default for BuildConfig {
+    fn default() -> Self {
+        Self {
+            sapling_padding: BundlePaddingRule::default(),
+            orchard_padding: BundlePaddingRule::default(),
+            // Shuffling spends/outputs before finalizing is the safer default: it stops
+            // on-chain ordering from leaking which notes a wallet chose to spend, or
+            // which output is the change.
+            shuffle_bundle_order: true,
+        }
+    }
+}
+
+impl BuildConfig {
+    /// Returns this config with the given Sapling padding rule, leaving its other
+    /// settings unchanged.
+    pub fn with_sapling_padding(mut self, padding: BundlePaddingRule) -> Self {
+        self.sapling_padding = padding;
+        self
+    }
+
+    /// Returns this config with the given Orchard padding rule, leaving its other
+    /// settings unchanged.
+    pub fn with_orchard_padding(mut self, padding: BundlePaddingRule) -> Self {
+        self.orchard_padding = padding;
+        self
+    }
+
+    /// Returns a config with shielded spends/outputs, transparent inputs/outputs, and
+    /// Orchard actions shuffled (`true`) or left in insertion order (`false`).
+    ///
+    /// Disable this for tests or other callers that need a deterministic action order,
+    /// e.g. to match a precomputed layout.
+    pub fn with_shuffle_bundle_order(mut self, shuffle: bool) -> Self {
+        self.shuffle_bundle_order = shuffle;
+        self
+    }
+}
+
 /// Reports on the progress made by the builder towards building a transaction.
 pub struct Progress {
     /// The number of steps completed.
@@ -139,6 +630,296 @@ impl Progress {
 
 enum ChangeAddress {
     SaplingChangeAddress(OutgoingViewingKey, PaymentAddress),
+    OrchardChangeAddress(orchard::keys::OutgoingViewingKey, orchard::Address),
+    /// A unified change address: change is sent to whichever pool the transaction
+    /// already uses, preferring Orchard.
+    UnifiedChangeAddress {
+        sapling: Option<(OutgoingViewingKey, PaymentAddress)>,
+        orchard: Option<(orchard::keys::OutgoingViewingKey, orchard::Address)>,
+    },
+}
+
+/// A single change output to be created in a transaction, tagged with the shielded pool
+/// it belongs to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChangeValue {
+    Sapling(Amount),
+    Orchard(Amount),
+}
+
+impl ChangeValue {
+    /// Returns the value of this change output.
+    pub fn value(&self) -> Amount {
+        match self {
+            ChangeValue::Sapling(v) => *v,
+            ChangeValue::Orchard(v) => *v,
+        }
+    }
+}
+
+/// The fee to be paid by a transaction, along with the change outputs (if any) that
+/// bring its value balance to zero, as computed by a [`ChangeStrategy`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TransactionBalance {
+    fee: Amount,
+    change: Vec<ChangeValue>,
+}
+
+impl TransactionBalance {
+    /// Constructs a new balance from its constituent parts.
+    pub fn new(fee: Amount, change: Vec<ChangeValue>) -> Self {
+        Self { fee, change }
+    }
+
+    /// Returns the fee computed for the transaction.
+    pub fn fee(&self) -> Amount {
+        self.fee
+    }
+
+    /// Returns the change outputs computed for the transaction.
+    pub fn change(&self) -> &[ChangeValue] {
+        &self.change
+    }
+}
+
+/// A strategy for computing the fee to be paid by a transaction, given the shape of its
+/// assembled components, and for distributing any resulting change across the shielded
+/// pools present in the transaction.
+///
+/// This replaces reliance on a single fixed `fee: Amount`, allowing the `Builder` to
+/// charge a fee that actually reflects the size and shape of the transaction being built.
+pub trait ChangeStrategy {
+    /// Computes the fee required for a transaction with the given shape, and the change
+    /// outputs (if any) required to bring `value_balance` (the net value of the
+    /// transaction's inputs and outputs, excluding fee and change) to zero.
+    ///
+    /// `transparent_in_sizes` and `transparent_out_sizes` are the serialized sizes, in
+    /// bytes, of the transparent inputs and outputs already added to the builder.
+    fn compute_balance(
+        &self,
+        transparent_in_sizes: &[usize],
+        transparent_out_sizes: &[usize],
+        sapling_spends: usize,
+        sapling_outputs: usize,
+        orchard_actions: usize,
+        value_balance: Amount,
+    ) -> Result<TransactionBalance, Error>;
+}
+
+/// A [`ChangeStrategy`] that charges a single fixed fee and sends all change to the
+/// Sapling pool, preserving the `Builder`'s historical behavior.
+pub struct BasicFixedFeeChangeStrategy {
+    fee: Amount,
+}
+
+impl BasicFixedFeeChangeStrategy {
+    /// Constructs a change strategy that charges the given fixed fee.
+    pub fn new(fee: Amount) -> Self {
+        Self { fee }
+    }
+}
+
+impl ChangeStrategy for BasicFixedFeeChangeStrategy {
+    fn compute_balance(
+        &self,
+        _transparent_in_sizes: &[usize],
+        _transparent_out_sizes: &[usize],
+        _sapling_spends: usize,
+        _sapling_outputs: usize,
+        _orchard_actions: usize,
+        value_balance: Amount,
+    ) -> Result<TransactionBalance, Error> {
+        let change = (value_balance - self.fee).ok_or(Error::InvalidAmount)?;
+        if change.is_negative() {
+            return Err(Error::InsufficientFunds {
+                available: value_balance,
+                required: self.fee,
+            });
+        }
+
+        let change = if change.is_positive() {
+            vec![ChangeValue::Sapling(change)]
+        } else {
+            vec![]
+        };
+
+        Ok(TransactionBalance::new(self.fee, change))
+    }
+}
+
+/// The ZIP 317 marginal fee, in zatoshis.
+const ZIP317_MARGINAL_FEE: u64 = 5000;
+
+/// The ZIP 317 number of grace actions below which no fee is charged for the "shape" of
+/// the transaction.
+const ZIP317_GRACE_ACTIONS: usize = 2;
+
+/// A [`ChangeStrategy`] implementing the ZIP 317 conventional fee:
+/// `marginal_fee * max(grace_actions, logical_actions)`, where `logical_actions` is the
+/// sum of the logical transparent input/output counts and the larger of the Sapling
+/// spend/output counts, plus the number of Orchard actions.
+pub struct Zip317FeeRule;
+
+impl Zip317FeeRule {
+    /// Constructs a new ZIP 317 fee rule using the standard marginal fee and grace
+    /// action count.
+    pub fn new() -> Self {
+        Zip317FeeRule
+    }
+
+    fn conventional_fee(
+        &self,
+        transparent_in_sizes: &[usize],
+        transparent_out_sizes: &[usize],
+        sapling_spends: usize,
+        sapling_outputs: usize,
+        orchard_actions: usize,
+    ) -> Amount {
+        let tin_logical = div_ceil(transparent_in_sizes.iter().sum(), 150);
+        let tout_logical = div_ceil(transparent_out_sizes.iter().sum(), 34);
+        let logical_actions =
+            tin_logical + tout_logical + std::cmp::max(sapling_spends, sapling_outputs) + orchard_actions;
+
+        let marginal_actions = std::cmp::max(ZIP317_GRACE_ACTIONS, logical_actions);
+        Amount::from_u64(marginal_actions as u64 * ZIP317_MARGINAL_FEE)
+            .expect("conventional fee fits in an Amount")
+    }
+}
+
+impl Default for Zip317FeeRule {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn div_ceil(numerator: usize, denominator: usize) -> usize {
+    if numerator == 0 {
+        0
+    } else {
+        (numerator - 1) / denominator + 1
+    }
+}
+
+impl ChangeStrategy for Zip317FeeRule {
+    fn compute_balance(
+        &self,
+        transparent_in_sizes: &[usize],
+        transparent_out_sizes: &[usize],
+        sapling_spends: usize,
+        sapling_outputs: usize,
+        orchard_actions: usize,
+        value_balance: Amount,
+    ) -> Result<TransactionBalance, Error> {
+        let fee = self.conventional_fee(
+            transparent_in_sizes,
+            transparent_out_sizes,
+            sapling_spends,
+            sapling_outputs,
+            orchard_actions,
+        );
+
+        let change = (value_balance - fee).ok_or(Error::InvalidAmount)?;
+        if change.is_negative() {
+            return Err(Error::InsufficientFunds {
+                available: value_balance,
+                required: fee,
+            });
+        }
+
+        Ok(TransactionBalance::new(
+            fee,
+            route_change(change, orchard_actions, sapling_spends, sapling_outputs),
+        ))
+    }
+}
+
+/// Routes a positive change value to Orchard if the transaction has Orchard actions and
+/// no Sapling spends or outputs, and to Sapling otherwise; returns no change output at
+/// all for a zero (or negative) change value.
+///
+/// Shared by [`Zip317FeeRule`] and [`SizeBasedFeeRule`], since they only disagree on how
+/// the fee itself is computed, not on where change should go once it's known.
+fn route_change(
+    change: Amount,
+    orchard_actions: usize,
+    sapling_spends: usize,
+    sapling_outputs: usize,
+) -> Vec<ChangeValue> {
+    if change.is_positive() {
+        if orchard_actions > 0 && sapling_spends == 0 && sapling_outputs == 0 {
+            vec![ChangeValue::Orchard(change)]
+        } else {
+            vec![ChangeValue::Sapling(change)]
+        }
+    } else {
+        vec![]
+    }
+}
+
+/// A pluggable rule for computing the fee required by a transaction, given the shape of
+/// its assembled components.
+///
+/// A [`ChangeStrategy`] that charges for the transparent component of a transaction by
+/// its serialized size, and for the shielded component as a marginal fee per Sapling
+/// spend, Sapling output, or Orchard action, where a count below the grace allowance is
+/// charged as the grace allowance.
+///
+/// This is a simpler cousin of [`Zip317FeeRule`]: it charges transparent data by raw
+/// byte count rather than converting it to a logical action count, and it counts Sapling
+/// spends and outputs together rather than taking the larger of the two. It uses the
+/// same marginal fee, grace allowance, and change-routing policy as [`Zip317FeeRule`].
+pub struct SizeBasedFeeRule;
+
+impl SizeBasedFeeRule {
+    /// Constructs a new size/logical-action-based fee rule.
+    pub fn new() -> Self {
+        SizeBasedFeeRule
+    }
+}
+
+impl Default for SizeBasedFeeRule {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The fee charged per byte of transparent input/output data by [`SizeBasedFeeRule`].
+const TRANSPARENT_FEE_PER_BYTE: u64 = 1;
+
+impl ChangeStrategy for SizeBasedFeeRule {
+    fn compute_balance(
+        &self,
+        transparent_in_sizes: &[usize],
+        transparent_out_sizes: &[usize],
+        sapling_spends: usize,
+        sapling_outputs: usize,
+        orchard_actions: usize,
+        value_balance: Amount,
+    ) -> Result<TransactionBalance, Error> {
+        let transparent_size: u64 = (transparent_in_sizes.iter().sum::<usize>()
+            + transparent_out_sizes.iter().sum::<usize>()) as u64;
+        let shielded_actions = sapling_spends + sapling_outputs + orchard_actions;
+        let marginal_actions = std::cmp::max(ZIP317_GRACE_ACTIONS, shielded_actions);
+
+        let fee = Amount::from_u64(
+            transparent_size * TRANSPARENT_FEE_PER_BYTE
+                + marginal_actions as u64 * ZIP317_MARGINAL_FEE,
+        )
+        .expect("fee fits in an Amount");
+
+        let change = (value_balance - fee).ok_or(Error::InvalidAmount)?;
+        if change.is_negative() {
+            return Err(Error::InsufficientFunds {
+                available: value_balance,
+                required: fee,
+            });
+        }
+
+        Ok(TransactionBalance::new(
+            fee,
+            route_change(change, orchard_actions, sapling_spends, sapling_outputs),
+        ))
+    }
 }
 
 /// Generates a [`Transaction`] from its inputs and outputs.
@@ -154,6 +935,7 @@ pub struct Builder<'a, P, R, O: UseOrchard = NoOrchardBuilder> {
     orchard_builder: O,
     orchard_spending_keys: Vec<orchard::keys::SpendAuthorizingKey>, // We need these for signatures
     change_address: Option<ChangeAddress>,
+    build_config: BuildConfig,
     #[cfg(feature = "zfuture")]
     tze_builder: TzeBuilder<'a, TransactionData<Unauthorized>>,
     #[cfg(not(feature = "zfuture"))]
@@ -226,6 +1008,7 @@ impl<'a, P: consensus::Parameters, R: RngCore + CryptoRng, O: UseOrchard> Builde
             orchard_builder,
             orchard_spending_keys: Vec::new(),
             change_address: None,
+            build_config: BuildConfig::default(),
             #[cfg(feature = "zfuture")]
             tze_builder: TzeBuilder::empty(),
             #[cfg(not(feature = "zfuture"))]
@@ -291,6 +1074,55 @@ impl<'a, P: consensus::Parameters, R: RngCore + CryptoRng> Builder<'a, P, R, Orc
     }
 }
 
+/// Routes a change output to the Orchard pool.
+///
+/// [`Builder::add_orchard_output`] is only defined for `Builder<'a, P, R, OrchardBuilder>`,
+/// but [`Builder::resolve_change_output`] and [`Builder::build_with_change_strategy`] are
+/// generic over any `O: UseOrchard` and need to route change to Orchard without knowing
+/// which concrete `O` they're working with. This trait bridges the two: it's implemented
+/// for both concrete builder configurations actually in use, so those generic methods can
+/// require `Self: RouteOrchardChange` and call through it instead of calling
+/// `add_orchard_output` directly.
+trait RouteOrchardChange {
+    fn add_orchard_change_output(
+        &mut self,
+        ovk: orchard::keys::OutgoingViewingKey,
+        recipient: orchard::Address,
+        value: Amount,
+    ) -> Result<(), Error>;
+}
+
+impl<'a, P: consensus::Parameters, R: RngCore + CryptoRng> RouteOrchardChange
+    for Builder<'a, P, R, NoOrchardBuilder>
+{
+    fn add_orchard_change_output(
+        &mut self,
+        _ovk: orchard::keys::OutgoingViewingKey,
+        _recipient: orchard::Address,
+        _value: Amount,
+    ) -> Result<(), Error> {
+        // A builder with no Orchard support can never have set `contains_orchard`, but
+        // it can be given an Orchard-bearing `ChangeAddress` through
+        // `send_orchard_change_to` or `send_unified_change_to`; routing change to it
+        // fails with NU5Inactive, the existing error for "no Orchard bundle to route
+        // Orchard change into".
+        Err(Error::NU5Inactive)
+    }
+}
+
+impl<'a, P: consensus::Parameters, R: RngCore + CryptoRng> RouteOrchardChange
+    for Builder<'a, P, R, OrchardBuilder>
+{
+    fn add_orchard_change_output(
+        &mut self,
+        ovk: orchard::keys::OutgoingViewingKey,
+        recipient: orchard::Address,
+        value: Amount,
+    ) -> Result<(), Error> {
+        self.add_orchard_output(Some(ovk), recipient, u64::from(value), MemoBytes::empty())
+    }
+}
+
 impl<'a, P: consensus::Parameters, R: RngCore + CryptoRng, O: UseOrchard> Builder<'a, P, R, O> {
     /// Adds a Sapling note to be spent in this transaction.
     ///
@@ -309,6 +1141,11 @@ impl<'a, P: consensus::Parameters, R: RngCore + CryptoRng, O: UseOrchard> Builde
     }
 
     /// Adds a Sapling address to send funds to.
+    ///
+    /// The note's `Rseed` encoding is chosen automatically (ZIP 212's `AfterZip212` once
+    /// Canopy is active at `target_height`, `BeforeZip212` otherwise) rather than being
+    /// left to the caller, so notes built for a given height always decrypt correctly at
+    /// that height.
     pub fn add_sapling_output(
         &mut self,
         ovk: Option<OutgoingViewingKey>,
@@ -317,7 +1154,7 @@ impl<'a, P: consensus::Parameters, R: RngCore + CryptoRng, O: UseOrchard> Builde
         memo: MemoBytes,
     ) -> Result<(), Error> {
         self.sapling_builder
-            .add_output(&mut self.rng, ovk, to, value, memo)
+            .add_output(&mut self.rng, self.target_height, ovk, to, value, memo)
             .map_err(Error::SaplingBuild)
     }
 
@@ -346,6 +1183,38 @@ impl<'a, P: consensus::Parameters, R: RngCore + CryptoRng, O: UseOrchard> Builde
             .map_err(Error::TransparentBuild)
     }
 
+    /// Sets the padding rule applied to the Sapling bundle when it is finalized.
+    ///
+    /// By default, Sapling bundles are padded to a minimum of two outputs so that a
+    /// transaction with a single real output doesn't reveal that fact on-chain.
+    pub fn set_sapling_padding(&mut self, padding: BundlePaddingRule) {
+        self.build_config.sapling_padding = padding;
+    }
+
+    /// Sets the padding rule applied to the Orchard bundle when it is finalized.
+    ///
+    /// By default, Orchard bundles are padded to the ZIP 317 grace-action count.
+    pub fn set_orchard_padding(&mut self, padding: BundlePaddingRule) {
+        self.build_config.orchard_padding = padding;
+    }
+
+    /// Sets whether shielded spends/outputs, transparent inputs/outputs, and Orchard
+    /// actions are shuffled into a random order when the transaction is finalized.
+    ///
+    /// This defaults to `true`: leaving spends and outputs in the order they were added
+    /// can leak which note a wallet chose to spend or which output is the change, since
+    /// that order is otherwise a simple function of call order. The [`SaplingMetadata`]
+    /// returned from a `build*` call always reports indices into the *final*, shuffled
+    /// bundle, so callers can still recover which output ended up where.
+    pub fn set_shuffle_bundle_order(&mut self, shuffle: bool) {
+        self.build_config.shuffle_bundle_order = shuffle;
+    }
+
+    /// Replaces the builder's entire [`BuildConfig`] at once.
+    pub fn set_build_config(&mut self, config: BuildConfig) {
+        self.build_config = config;
+    }
+
     /// Sets the Sapling address to which any change will be sent.
     ///
     /// By default, change is sent to the Sapling address corresponding to the first note
@@ -354,6 +1223,29 @@ impl<'a, P: consensus::Parameters, R: RngCore + CryptoRng, O: UseOrchard> Builde
         self.change_address = Some(ChangeAddress::SaplingChangeAddress(ovk, to))
     }
 
+    /// Sets the Orchard address to which any change will be sent.
+    ///
+    /// Use this (or [`Builder::send_unified_change_to`]) for Orchard-only transactions,
+    /// which have no Sapling address to fall back on.
+    pub fn send_orchard_change_to(
+        &mut self,
+        ovk: orchard::keys::OutgoingViewingKey,
+        to: orchard::Address,
+    ) {
+        self.change_address = Some(ChangeAddress::OrchardChangeAddress(ovk, to))
+    }
+
+    /// Sets a unified change address covering both pools. When change is created, the
+    /// `Builder` picks whichever pool's receiver is present and already has spends or
+    /// outputs in the transaction, preferring Orchard.
+    pub fn send_unified_change_to(
+        &mut self,
+        sapling: Option<(OutgoingViewingKey, PaymentAddress)>,
+        orchard: Option<(orchard::keys::OutgoingViewingKey, orchard::Address)>,
+    ) {
+        self.change_address = Some(ChangeAddress::UnifiedChangeAddress { sapling, orchard })
+    }
+
     /// Sets the notifier channel, where progress of building the transaction is sent.
     ///
     /// An update is sent after every Spend or Output is computed, and the `u32` sent
@@ -390,19 +1282,68 @@ impl<'a, P: consensus::Parameters, R: RngCore + CryptoRng, O: UseOrchard> Builde
         self.fee = custom_fee;
     }
 
+    /// Adds a change output of the given value, routing it to whichever pool
+    /// [`Builder::send_change_to`]/[`Builder::send_orchard_change_to`]/
+    /// [`Builder::send_unified_change_to`] selected.
+    ///
+    /// If no change address was set, change is sent to the Orchard address of the first
+    /// Orchard spend if the transaction has no Sapling spends or outputs (so that an
+    /// Orchard-only transaction has somewhere to send change), and to the Sapling address
+    /// of the first Sapling spend otherwise.
+    fn resolve_change_output(&mut self, change: Amount) -> Result<(), Error>
+    where
+        Self: RouteOrchardChange,
+    {
+        match self.change_address.take() {
+            Some(ChangeAddress::SaplingChangeAddress(ovk, addr)) => {
+                self.add_sapling_output(Some(ovk), addr, change, MemoBytes::empty())?;
+            }
+            Some(ChangeAddress::OrchardChangeAddress(ovk, addr)) => {
+                self.add_orchard_change_output(ovk, addr, change)?;
+            }
+            Some(ChangeAddress::UnifiedChangeAddress { sapling, orchard }) => {
+                let sapling_in_use = self.sapling_builder.spend_count() > 0
+                    || self.sapling_builder.output_count() > 0;
+                if orchard.is_some() && (self.contains_orchard || !sapling_in_use) {
+                    let (ovk, addr) = orchard.ok_or(Error::NoChangeAddress)?;
+                    self.add_orchard_change_output(ovk, addr, change)?;
+                } else {
+                    let (ovk, addr) = sapling.ok_or(Error::NoChangeAddress)?;
+                    self.add_sapling_output(Some(ovk), addr, change, MemoBytes::empty())?;
+                }
+            }
+            None => {
+                let sapling_in_use = self.sapling_builder.spend_count() > 0
+                    || self.sapling_builder.output_count() > 0;
+                if self.contains_orchard && !sapling_in_use {
+                    let (ovk, addr) = self
+                        .orchard_builder
+                        .get_candidate_change_address()
+                        .ok_or(Error::NoChangeAddress)?;
+                    self.add_orchard_change_output(ovk, addr, change)?;
+                } else {
+                    let (ovk, addr) = self
+                        .sapling_builder
+                        .get_candidate_change_address()
+                        .ok_or(Error::NoChangeAddress)?;
+                    self.add_sapling_output(Some(ovk), addr, change, MemoBytes::empty())?;
+                }
+            }
+        }
+        Ok(())
+    }
+
     /// Builds a transaction from the configured spends and outputs.
     ///
     /// Upon success, returns a tuple containing the final transaction, and the
     /// [`SaplingMetadata`] generated during the build process.
     pub fn build(
-        mut self,
-        prover: &impl TxProver
-    ) -> Result<(Transaction, SaplingMetadata), Error> {
-        let consensus_branch_id = BranchId::for_height(&self.params, self.target_height);
-
-        // determine transaction version
-        let version = TxVersion::suggested_for_branch(consensus_branch_id);
-
+        mut self,
+        prover: &impl TxProver
+    ) -> Result<(Transaction, SaplingMetadata), Error>
+    where
+        Self: RouteOrchardChange,
+    {
         //
         // Consistency checks
         //
@@ -422,23 +1363,29 @@ impl<'a, P: consensus::Parameters, R: RngCore + CryptoRng, O: UseOrchard> Builde
         //
 
         if change.is_positive() {
-            // Send change to the specified change address. If no change address
-            // was set, send change to the first Sapling address given as input.
-            match self.change_address.take() {
-                Some(ChangeAddress::SaplingChangeAddress(ovk, addr)) => {
-                    self.add_sapling_output(Some(ovk), addr, change, MemoBytes::empty())?;
-                }
-                None => {
-                    let (ovk, addr) = self
-                        .sapling_builder
-                        .get_candidate_change_address()
-                        .ok_or(Error::NoChangeAddress)?;
-                    self.add_sapling_output(Some(ovk), addr, change, MemoBytes::empty())?;
-                }
-            }
+            self.resolve_change_output(change)?;
         }
 
-        let transparent_bundle = self.transparent_builder.build();
+        self.finalize_locally_signed(prover)
+    }
+
+    /// Assembles the transparent, Sapling, and (if present) Orchard bundles from the
+    /// spends/outputs collected so far, then immediately applies this builder's own
+    /// proving/signing material to produce a fully authorized [`Transaction`].
+    ///
+    /// This is the tail shared by every entry point that signs with keys held directly
+    /// by the builder; it assumes the change output (if any) has already been resolved
+    /// by the caller. [`Builder::build_for_external_signing`] and
+    /// [`Builder::build_unauthorized`] stop short of this point instead, handing the
+    /// unauthorized bundles to an external signer.
+    fn finalize_locally_signed(
+        mut self,
+        prover: &impl TxProver,
+    ) -> Result<(Transaction, SaplingMetadata), Error> {
+        let consensus_branch_id = BranchId::for_height(&self.params, self.target_height);
+        let version = TxVersion::suggested_for_branch(consensus_branch_id);
+
+        let transparent_bundle = self.transparent_builder.build(self.build_config.shuffle_bundle_order);
 
         let mut rng = self.rng;
         let mut ctx = prover.new_sapling_proving_context();
@@ -449,6 +1396,8 @@ impl<'a, P: consensus::Parameters, R: RngCore + CryptoRng, O: UseOrchard> Builde
                 &mut ctx,
                 &mut rng,
                 self.target_height,
+                self.build_config.sapling_padding.target(2),
+                self.build_config.shuffle_bundle_order,
                 self.progress_notifier.as_ref(),
             )
             .map_err(Error::SaplingBuild)?;
@@ -456,7 +1405,7 @@ impl<'a, P: consensus::Parameters, R: RngCore + CryptoRng, O: UseOrchard> Builde
         // Build orchard only if there are any items in the orchard bundle.
         let orchard_bundle: Option<orchard::Bundle<_, Amount>> = if self.contains_orchard {
             self.orchard_builder
-                .build(&mut rng)
+                .build(&mut rng, self.build_config.orchard_padding.target(ZIP317_GRACE_ACTIONS), self.build_config.shuffle_bundle_order)
                 .transpose()
                 .map_err(Error::OrchardBuild)?
         } else {
@@ -468,7 +1417,7 @@ impl<'a, P: consensus::Parameters, R: RngCore + CryptoRng, O: UseOrchard> Builde
 
         let unauthed_tx: TransactionData<Unauthorized> = TransactionData {
             version,
-            consensus_branch_id: BranchId::for_height(&self.params, self.target_height),
+            consensus_branch_id,
             lock_time: 0,
             expiry_height: self.expiry_height,
             transparent_bundle,
@@ -553,6 +1502,358 @@ impl<'a, P: consensus::Parameters, R: RngCore + CryptoRng, O: UseOrchard> Builde
         // of freeze() should be infalliable.
         Ok((authorized_tx.freeze().unwrap(), tx_metadata))
     }
+
+    /// Assembles the transaction from the configured spends and outputs, but stops short
+    /// of creating the Sapling Spend/Output proofs, returning an [`UnprovenTransaction`]
+    /// instead.
+    ///
+    /// This lets the (possibly expensive) proving step be performed out of band from
+    /// note selection and bundle assembly: on a different thread, on a different
+    /// machine, or via a batching prover. Call [`UnprovenTransaction::prove`] with a
+    /// [`SpendProver`]/[`OutputProver`] pair to obtain the final, signed [`Transaction`].
+    pub fn build_unproven(mut self) -> Result<(UnprovenTransaction, SaplingMetadata), Error>
+    where
+        Self: RouteOrchardChange,
+    {
+        let consensus_branch_id = BranchId::for_height(&self.params, self.target_height);
+        let version = TxVersion::suggested_for_branch(consensus_branch_id);
+
+        let change = (self.value_balance()? - self.fee).ok_or(Error::InvalidAmount)?;
+        if change.is_negative() {
+            return Err(Error::ChangeIsNegative(change));
+        }
+
+        if change.is_positive() {
+            self.resolve_change_output(change)?;
+        }
+
+        let transparent_bundle = self.transparent_builder.build(self.build_config.shuffle_bundle_order);
+
+        let mut rng = self.rng;
+        let (unproven_sapling_bundle, sapling_meta) = self
+            .sapling_builder
+            .build_unproven(
+                &mut rng,
+                self.target_height,
+                self.build_config.sapling_padding.target(2),
+                self.build_config.shuffle_bundle_order,
+                self.progress_notifier.as_ref(),
+            )
+            .map_err(Error::SaplingBuild)?;
+
+        let orchard_bundle: Option<orchard::Bundle<orchard::bundle::Authorized, Amount>> =
+            if self.contains_orchard {
+                self.orchard_builder
+                    .build(&mut rng, self.build_config.orchard_padding.target(ZIP317_GRACE_ACTIONS), self.build_config.shuffle_bundle_order)
+                    .transpose()
+                    .map_err(Error::OrchardBuild)?
+                    .map(|b| b.create_proof(&orchard::circuit::ProvingKey::build(), &mut rng))
+                    .transpose()
+                    .map_err(Error::OrchardBuild)?
+            } else {
+                None
+            };
+
+        #[cfg(feature = "zfuture")]
+        let (tze_bundle, _tze_signers) = self.tze_builder.build();
+
+        Ok((
+            UnprovenTransaction {
+                version,
+                consensus_branch_id,
+                expiry_height: self.expiry_height,
+                transparent_bundle,
+                unproven_sapling_bundle: Some(unproven_sapling_bundle),
+                orchard_bundle,
+                orchard_spending_keys: self.orchard_spending_keys,
+                #[cfg(feature = "zfuture")]
+                tze_bundle,
+            },
+            sapling_meta,
+        ))
+    }
+
+    /// Assembles the transaction and creates its Sapling/Orchard proofs, but stops
+    /// short of producing spend authorization or binding signatures, returning a
+    /// [`PartiallyBuiltTransaction`].
+    ///
+    /// This is intended for hardware-wallet / remote-signer flows: the caller
+    /// serializes the partially-built transaction (or just the data returned by its
+    /// sighash accessors), hands it to an external signer, and finalizes the result
+    /// with [`PartiallyBuiltTransaction::apply_external_signatures`].
+    pub fn build_for_external_signing(
+        mut self,
+        prover: &impl TxProver,
+    ) -> Result<PartiallyBuiltTransaction, Error>
+    where
+        Self: RouteOrchardChange,
+    {
+        let consensus_branch_id = BranchId::for_height(&self.params, self.target_height);
+        let version = TxVersion::suggested_for_branch(consensus_branch_id);
+
+        let change = (self.value_balance()? - self.fee).ok_or(Error::InvalidAmount)?;
+        if change.is_negative() {
+            return Err(Error::ChangeIsNegative(change));
+        }
+
+        if change.is_positive() {
+            self.resolve_change_output(change)?;
+        }
+
+        #[cfg(feature = "transparent-inputs")]
+        let transparent_coins = self.transparent_builder.input_coins().to_vec();
+        let transparent_bundle = self.transparent_builder.build(self.build_config.shuffle_bundle_order);
+
+        let mut rng = self.rng;
+        let mut ctx = prover.new_sapling_proving_context();
+        let (sapling_bundle, sapling_signing_parts, sapling_bsk) = self
+            .sapling_builder
+            .build_unauthorized(
+                prover,
+                &mut ctx,
+                &mut rng,
+                self.target_height,
+                self.build_config.sapling_padding.target(2),
+                self.build_config.shuffle_bundle_order,
+                self.progress_notifier.as_ref(),
+            )
+            .map_err(Error::SaplingBuild)?;
+
+        let (orchard_bundle, orchard_signing_parts): (
+            Option<orchard::Bundle<_, Amount>>,
+            Vec<OrchardSigningParts>,
+        ) = if self.contains_orchard {
+            match self
+                .orchard_builder
+                .build(&mut rng, self.build_config.orchard_padding.target(ZIP317_GRACE_ACTIONS), self.build_config.shuffle_bundle_order)
+                .transpose()
+                .map_err(Error::OrchardBuild)?
+            {
+                Some(bundle) => {
+                    let proven = bundle
+                        .create_proof(&orchard::circuit::ProvingKey::build(), &mut rng)
+                        .map_err(Error::OrchardBuild)?;
+                    let signing_parts = proven
+                        .actions()
+                        .iter()
+                        .map(|action| OrchardSigningParts {
+                            alpha: action.authorization().alpha,
+                        })
+                        .collect();
+                    (Some(proven), signing_parts)
+                }
+                None => (None, Vec::new()),
+            }
+        } else {
+            (None, Vec::new())
+        };
+
+        #[cfg(feature = "zfuture")]
+        let (tze_bundle, _tze_signers) = self.tze_builder.build();
+
+        let unauthed_tx: TransactionData<Unauthorized> = TransactionData {
+            version,
+            consensus_branch_id,
+            lock_time: 0,
+            expiry_height: self.expiry_height,
+            transparent_bundle,
+            sprout_bundle: None,
+            sapling_bundle,
+            orchard_bundle,
+            #[cfg(feature = "zfuture")]
+            tze_bundle,
+        };
+
+        let txid_parts = unauthed_tx.digest(TxIdDigester);
+
+        Ok(PartiallyBuiltTransaction {
+            unauthed_tx,
+            txid_parts,
+            #[cfg(feature = "transparent-inputs")]
+            transparent_coins,
+            sapling_signing_parts,
+            orchard_signing_parts,
+            bsk: sapling_bsk,
+        })
+    }
+
+    /// Assembles the transaction, creates its Sapling/Orchard proofs, and computes the
+    /// sighash, returning an [`UnauthorizedTransactionBundle`] that exposes the raw
+    /// per-spend randomizers (and the accumulated binding signing key) instead of
+    /// retaining any spend authorizing keys.
+    ///
+    /// This is the lower-level counterpart to [`Builder::build_for_external_signing`],
+    /// for HSMs or hardware wallets that hold only raw signing keys rather than this
+    /// crate's key types: call [`UnauthorizedTransactionBundle::apply_signatures`] with
+    /// the signatures the device computes over
+    /// [`UnauthorizedTransactionBundle::sighash`] to finish the transaction.
+    pub fn build_unauthorized(
+        mut self,
+        prover: &impl TxProver,
+    ) -> Result<UnauthorizedTransactionBundle, Error>
+    where
+        Self: RouteOrchardChange,
+    {
+        let consensus_branch_id = BranchId::for_height(&self.params, self.target_height);
+        let version = TxVersion::suggested_for_branch(consensus_branch_id);
+
+        let change = (self.value_balance()? - self.fee).ok_or(Error::InvalidAmount)?;
+        if change.is_negative() {
+            return Err(Error::ChangeIsNegative(change));
+        }
+
+        if change.is_positive() {
+            self.resolve_change_output(change)?;
+        }
+
+        let transparent_bundle = self.transparent_builder.build(self.build_config.shuffle_bundle_order);
+
+        let mut rng = self.rng;
+        let mut ctx = prover.new_sapling_proving_context();
+        let (sapling_bundle, sapling_signing_parts, sapling_bsk) = self
+            .sapling_builder
+            .build_unauthorized(
+                prover,
+                &mut ctx,
+                &mut rng,
+                self.target_height,
+                self.build_config.sapling_padding.target(2),
+                self.build_config.shuffle_bundle_order,
+                self.progress_notifier.as_ref(),
+            )
+            .map_err(Error::SaplingBuild)?;
+
+        let (orchard_bundle, orchard_signing_parts): (
+            Option<orchard::Bundle<_, Amount>>,
+            Vec<OrchardSigningParts>,
+        ) = if self.contains_orchard {
+            match self
+                .orchard_builder
+                .build(&mut rng, self.build_config.orchard_padding.target(ZIP317_GRACE_ACTIONS), self.build_config.shuffle_bundle_order)
+                .transpose()
+                .map_err(Error::OrchardBuild)?
+            {
+                Some(bundle) => {
+                    let proven = bundle
+                        .create_proof(&orchard::circuit::ProvingKey::build(), &mut rng)
+                        .map_err(Error::OrchardBuild)?;
+                    // Each action's authorization still carries the `alpha` used to
+                    // randomize its spend authorizing key when the action was built;
+                    // `create_proof` only attaches the proof, it doesn't discard it.
+                    let signing_parts = proven
+                        .actions()
+                        .iter()
+                        .map(|action| OrchardSigningParts {
+                            alpha: action.authorization().alpha,
+                        })
+                        .collect();
+                    (Some(proven), signing_parts)
+                }
+                None => (None, Vec::new()),
+            }
+        } else {
+            (None, Vec::new())
+        };
+
+        #[cfg(feature = "zfuture")]
+        let (tze_bundle, _tze_signers) = self.tze_builder.build();
+
+        let unauthed_tx: TransactionData<Unauthorized> = TransactionData {
+            version,
+            consensus_branch_id,
+            lock_time: 0,
+            expiry_height: self.expiry_height,
+            transparent_bundle,
+            sprout_bundle: None,
+            sapling_bundle,
+            orchard_bundle,
+            #[cfg(feature = "zfuture")]
+            tze_bundle,
+        };
+
+        let txid_parts = unauthed_tx.digest(TxIdDigester);
+        let sighash = signature_hash(&unauthed_tx, &SignableInput::Shielded, &txid_parts);
+
+        Ok(UnauthorizedTransactionBundle {
+            unauthed_tx,
+            sighash,
+            sapling_signing_parts,
+            orchard_signing_parts,
+            bsk: sapling_bsk,
+        })
+    }
+
+    /// Builds a transaction from the configured spends and outputs, computing the fee
+    /// and change via the given [`ChangeStrategy`] rather than the builder's fixed `fee`.
+    ///
+    /// This supersedes [`Builder::build`] for callers that want the fee to reflect the
+    /// actual shape of the transaction (for example, a [`Zip317FeeRule`]) instead of a
+    /// caller-supplied constant. [`BasicFixedFeeChangeStrategy`] charges a fixed fee as
+    /// [`Builder::build`] does, but it always places change in the Sapling pool and
+    /// reports a shortfall as [`Error::InsufficientFunds`] rather than
+    /// [`Error::ChangeIsNegative`].
+    ///
+    /// The strategy picks the pool of each change output, except that a change address
+    /// set with [`Builder::send_change_to`] or [`Builder::send_orchard_change_to`] always
+    /// receives the change in its own pool.
+    pub fn build_with_change_strategy(
+        mut self,
+        prover: &impl TxProver,
+        change_strategy: &impl ChangeStrategy,
+    ) -> Result<(Transaction, SaplingMetadata), Error>
+    where
+        Self: RouteOrchardChange,
+    {
+        //
+        // Compute fee and change via the pluggable strategy
+        //
+
+        let balance = change_strategy.compute_balance(
+            &self.transparent_builder.input_sizes(),
+            &self.transparent_builder.output_sizes(),
+            self.sapling_builder.spend_count(),
+            self.sapling_builder.output_count(),
+            self.orchard_builder.action_count(),
+            self.value_balance()?,
+        )?;
+
+        for change_value in balance.change() {
+            match change_value {
+                ChangeValue::Sapling(value) => {
+                    let (ovk, addr) = match self.change_address.take() {
+                        Some(ChangeAddress::SaplingChangeAddress(ovk, addr)) => (ovk, addr),
+                        Some(ChangeAddress::UnifiedChangeAddress { sapling: Some(s), .. }) => s,
+                        Some(ChangeAddress::OrchardChangeAddress(ovk, addr)) => {
+                            self.add_orchard_change_output(ovk, addr, *value)?;
+                            continue;
+                        }
+                        _ => self
+                            .sapling_builder
+                            .get_candidate_change_address()
+                            .ok_or(Error::NoChangeAddress)?,
+                    };
+                    self.add_sapling_output(Some(ovk), addr, *value, MemoBytes::empty())?;
+                }
+                ChangeValue::Orchard(value) => {
+                    let (ovk, addr) = match self.change_address.take() {
+                        Some(ChangeAddress::OrchardChangeAddress(ovk, addr)) => (ovk, addr),
+                        Some(ChangeAddress::UnifiedChangeAddress { orchard: Some(o), .. }) => o,
+                        Some(ChangeAddress::SaplingChangeAddress(ovk, addr)) => {
+                            self.add_sapling_output(Some(ovk), addr, *value, MemoBytes::empty())?;
+                            continue;
+                        }
+                        _ => self
+                            .orchard_builder
+                            .get_candidate_change_address()
+                            .ok_or(Error::NoChangeAddress)?,
+                    };
+                    self.add_orchard_change_output(ovk, addr, *value)?;
+                }
+            }
+        }
+
+        self.finalize_locally_signed(prover)
+    }
 }
 
 #[cfg(feature = "zfuture")]
@@ -649,7 +1950,11 @@ mod tests {
         zip32::{ExtendedFullViewingKey, ExtendedSpendingKey},
     };
 
-    use super::{Builder, Error, SaplingBuilder, DEFAULT_TX_EXPIRY_DELTA};
+    use super::{
+        route_change, BuildConfig, Builder, ChangeStrategy, ChangeValue, Error, SaplingBuilder,
+        SizeBasedFeeRule, Zip317FeeRule, DEFAULT_TX_EXPIRY_DELTA, ZIP317_GRACE_ACTIONS,
+        ZIP317_MARGINAL_FEE,
+    };
 
     #[cfg(feature = "zfuture")]
     use super::TzeBuilder;
@@ -702,6 +2007,7 @@ mod tests {
             orchard_builder: NoOrchardBuilder,
             orchard_spending_keys: Vec::new(),
             change_address: None,
+            build_config: BuildConfig::default(),
             #[cfg(feature = "zfuture")]
             tze_builder: TzeBuilder::empty(),
             #[cfg(not(feature = "zfuture"))]
@@ -772,6 +2078,124 @@ mod tests {
         );
     }
 
+    #[test]
+    fn conventional_fee_charges_grace_actions_then_marginal_fee() {
+        let rule = Zip317FeeRule::new();
+
+        // Below the grace allowance, only the grace fee is charged.
+        assert_eq!(
+            rule.conventional_fee(&[], &[], 0, 0, 0),
+            Amount::from_u64(ZIP317_GRACE_ACTIONS as u64 * ZIP317_MARGINAL_FEE).unwrap()
+        );
+        assert_eq!(
+            rule.conventional_fee(&[], &[], 1, 1, 0),
+            Amount::from_u64(ZIP317_GRACE_ACTIONS as u64 * ZIP317_MARGINAL_FEE).unwrap()
+        );
+
+        // Above the grace allowance, the fee tracks the larger of Sapling spends/outputs
+        // plus transparent inputs/outputs (each rounded up to a logical action) plus
+        // Orchard actions.
+        assert_eq!(
+            rule.conventional_fee(&[150, 150], &[], 0, 0, 3),
+            Amount::from_u64(5 * ZIP317_MARGINAL_FEE).unwrap()
+        );
+        // 200 bytes of transparent input rounds up to 2 logical actions (150 bytes each).
+        assert_eq!(
+            rule.conventional_fee(&[200], &[], 0, 0, 0),
+            Amount::from_u64(ZIP317_GRACE_ACTIONS as u64 * ZIP317_MARGINAL_FEE).unwrap()
+        );
+    }
+
+    #[test]
+    fn size_based_fee_charges_transparent_bytes_plus_marginal_shielded_fee() {
+        let rule = SizeBasedFeeRule::new();
+        let value_balance = Amount::from_u64(1_000_000).unwrap();
+
+        // Transparent size is charged in full, at one zatoshi per byte; the shielded
+        // side is still below the grace allowance, so only the grace fee applies there.
+        let balance = rule
+            .compute_balance(&[100], &[50], 0, 0, 0, value_balance)
+            .unwrap();
+        assert_eq!(
+            balance.fee(),
+            Amount::from_u64(150 + ZIP317_GRACE_ACTIONS as u64 * ZIP317_MARGINAL_FEE).unwrap()
+        );
+
+        // Once shielded actions exceed the grace allowance, the marginal fee scales with
+        // the actual action count instead of the grace floor.
+        let balance = rule
+            .compute_balance(&[], &[], 2, 1, 1, value_balance)
+            .unwrap();
+        assert_eq!(
+            balance.fee(),
+            Amount::from_u64(4 * ZIP317_MARGINAL_FEE).unwrap()
+        );
+    }
+
+    /// Regression test for the Sapling spend/output shuffle: [`SaplingMetadata`] must
+    /// keep mapping each logical (call-order) index to the physical bundle position it
+    /// actually ended up at, even though that position was randomized.
+    #[test]
+    fn sapling_metadata_indices_survive_shuffle() {
+        use crate::transaction::builder::{self, TransparentBuilder};
+
+        let extsk = ExtendedSpendingKey::master(&[]);
+        let extfvk = ExtendedFullViewingKey::from(&extsk);
+        let ovk = Some(extfvk.fvk.ovk);
+        let to = extfvk.default_address().1;
+
+        let tx_height = TEST_NETWORK
+            .activation_height(NetworkUpgrade::Sapling)
+            .unwrap();
+
+        let build = |shuffle: bool| {
+            let mut builder = builder::Builder {
+                params: TEST_NETWORK,
+                rng: OsRng,
+                target_height: tx_height,
+                expiry_height: tx_height + DEFAULT_TX_EXPIRY_DELTA,
+                fee: Amount::zero(),
+                transparent_builder: TransparentBuilder::empty(),
+                sapling_builder: SaplingBuilder::new(TEST_NETWORK, tx_height),
+                contains_orchard: false,
+                orchard_builder: NoOrchardBuilder,
+                orchard_spending_keys: Vec::new(),
+                change_address: None,
+                build_config: BuildConfig::default().with_shuffle_bundle_order(shuffle),
+                #[cfg(feature = "zfuture")]
+                tze_builder: TzeBuilder::empty(),
+                #[cfg(not(feature = "zfuture"))]
+                tze_builder: PhantomData,
+                progress_notifier: None,
+            };
+
+            for _ in 0..4 {
+                builder
+                    .add_sapling_output(ovk, to.clone(), Amount::zero(), MemoBytes::empty())
+                    .unwrap();
+            }
+
+            let (_, sapling_meta) = builder.build_unproven().unwrap();
+            sapling_meta
+        };
+
+        // With shuffling disabled, the physical order matches call order exactly.
+        let unshuffled = build(false);
+        for n in 0..4 {
+            assert_eq!(unshuffled.output_index(n), Some(n));
+        }
+
+        // With shuffling enabled, every logical index must still resolve to exactly one
+        // physical position, and those positions must together cover the whole bundle --
+        // i.e. the mapping stays a bijection, it just need not be the identity.
+        let shuffled = build(true);
+        let mut physical_indices: Vec<usize> = (0..4)
+            .map(|n| shuffled.output_index(n).expect("every output was indexed"))
+            .collect();
+        physical_indices.sort_unstable();
+        assert_eq!(physical_indices, vec![0, 1, 2, 3]);
+    }
+
     #[test]
     fn fails_on_negative_change() {
         let mut rng = OsRng;
@@ -922,4 +2346,84 @@ mod tests {
             )
         }
     }
+
+    #[test]
+    fn route_change_prefers_orchard_only_when_no_sapling_activity() {
+        let value = Amount::from_u64(1000).unwrap();
+
+        // No change at all if there is nothing left over.
+        assert_eq!(route_change(Amount::zero(), 1, 0, 0), vec![]);
+        assert_eq!(route_change(Amount::from_i64(-1).unwrap(), 1, 0, 0), vec![]);
+
+        // Orchard actions are present, and neither Sapling spends nor outputs are: route
+        // change to Orchard.
+        assert_eq!(
+            route_change(value, 1, 0, 0),
+            vec![ChangeValue::Orchard(value)]
+        );
+
+        // A Sapling spend (even alongside Orchard actions) keeps change in Sapling.
+        assert_eq!(
+            route_change(value, 1, 1, 0),
+            vec![ChangeValue::Sapling(value)]
+        );
+
+        // A Sapling output (even alongside Orchard actions) keeps change in Sapling.
+        assert_eq!(
+            route_change(value, 1, 0, 1),
+            vec![ChangeValue::Sapling(value)]
+        );
+
+        // No Orchard actions at all: change falls back to Sapling.
+        assert_eq!(
+            route_change(value, 0, 0, 0),
+            vec![ChangeValue::Sapling(value)]
+        );
+    }
+
+    /// Regression test for [`Builder::build_unauthorized`] and
+    /// [`UnauthorizedTransactionBundle::apply_signatures`]: confirms the external-signing
+    /// delegation path is wired all the way through (matching randomizer count, bogus
+    /// signatures rejected rather than silently accepted or panicking).
+    #[test]
+    fn build_unauthorized_then_apply_signatures_rejects_bogus_signatures() {
+        let mut rng = OsRng;
+        let extsk = ExtendedSpendingKey::master(&[]);
+        let extfvk = ExtendedFullViewingKey::from(&extsk);
+        let ovk = Some(extfvk.fvk.ovk);
+        let to = extfvk.default_address().1;
+
+        let tx_height = TEST_NETWORK
+            .activation_height(NetworkUpgrade::Sapling)
+            .unwrap();
+
+        let note1 = to
+            .create_note(60000, Rseed::BeforeZip212(jubjub::Fr::random(&mut rng)))
+            .unwrap();
+        let cmu1 = Node::new(note1.cmu().to_repr());
+        let mut tree = CommitmentTree::empty();
+        tree.append(cmu1).unwrap();
+        let witness1 = IncrementalWitness::from_tree(&tree);
+
+        let mut builder = Builder::new(TEST_NETWORK, tx_height);
+        builder
+            .add_sapling_spend(extsk, *to.diversifier(), note1, witness1.path().unwrap())
+            .unwrap();
+        builder
+            .add_sapling_output(ovk, to, Amount::from_u64(30000).unwrap(), MemoBytes::empty())
+            .unwrap();
+
+        let bundle = builder.build_unauthorized(&MockTxProver).unwrap();
+
+        // One Sapling spend was added, so exactly one randomizer should have come back,
+        // and there is no Orchard bundle.
+        assert_eq!(bundle.sapling_signing_parts().len(), 1);
+        assert_eq!(bundle.orchard_signing_parts().len(), 0);
+
+        // Signatures that don't actually authorize the sighash must be rejected, not
+        // silently accepted or turned into a panic, no matter how they're sourced.
+        assert!(bundle
+            .apply_signatures(vec![[0u8; 64]], vec![], [0u8; 64])
+            .is_err());
+    }
 }